@@ -0,0 +1,2 @@
+-- case-insensitive text for name columns
+CREATE EXTENSION IF NOT EXISTS citext;
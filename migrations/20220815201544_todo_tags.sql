@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS todo_tags
+(
+    todo_id int     NOT NULL REFERENCES todo_todos (id) ON DELETE CASCADE,
+    tag     varchar NOT NULL,
+    PRIMARY KEY (todo_id, tag)
+)
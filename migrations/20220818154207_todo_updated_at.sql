@@ -0,0 +1,2 @@
+ALTER TABLE todo_todos
+    ADD COLUMN IF NOT EXISTS updated_at timestamptz NOT NULL DEFAULT now();
@@ -0,0 +1,2 @@
+-- free-form key/value properties
+CREATE EXTENSION IF NOT EXISTS hstore;
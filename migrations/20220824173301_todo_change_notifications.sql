@@ -0,0 +1,18 @@
+-- Announce every row change on the `todo_changes` channel. Payloads are capped at
+-- 8000 bytes, so only send the id and let listeners look the row up.
+CREATE OR REPLACE FUNCTION todo_notify_change() RETURNS trigger AS
+$$
+BEGIN
+    PERFORM pg_notify('todo_changes', json_build_object(
+            'op', lower(TG_OP),
+            'id', CASE WHEN TG_OP = 'DELETE' THEN OLD.id ELSE NEW.id END
+        )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER todo_changes
+    AFTER INSERT OR UPDATE OR DELETE
+    ON todo_todos
+    FOR EACH ROW
+EXECUTE FUNCTION todo_notify_change();
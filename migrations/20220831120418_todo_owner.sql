@@ -0,0 +1,4 @@
+ALTER TABLE todo_todos
+    ADD COLUMN IF NOT EXISTS owner text;
+
+CREATE INDEX IF NOT EXISTS todo_todos_owner ON todo_todos (owner);
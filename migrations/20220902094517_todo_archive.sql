@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS todo_archive
+(
+    id          int         PRIMARY KEY,
+    name        varchar     NOT NULL,
+    done        boolean     NOT NULL,
+    updated_at  timestamptz NOT NULL,
+    created_at  timestamptz NOT NULL,
+    due_date    date,
+    deleted_at  timestamptz,
+    owner       text,
+    archived_at timestamptz NOT NULL DEFAULT now()
+);
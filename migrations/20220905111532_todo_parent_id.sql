@@ -0,0 +1,7 @@
+ALTER TABLE todo_todos
+    ADD COLUMN IF NOT EXISTS parent_id int REFERENCES todo_todos (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS todo_todos_parent_id ON todo_todos (parent_id);
+
+ALTER TABLE todo_archive
+    ADD COLUMN IF NOT EXISTS parent_id int;
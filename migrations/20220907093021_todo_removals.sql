@@ -0,0 +1,25 @@
+-- When each owner's todos were deleted or handed to someone else, so their list's
+-- Last-Modified moves on removals too; `max(updated_at)` can't see a row that's gone.
+CREATE TABLE IF NOT EXISTS todo_removals
+(
+    owner      text,
+    removed_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS todo_removals_owner ON todo_removals (owner, removed_at);
+
+CREATE OR REPLACE FUNCTION todo_record_removal() RETURNS trigger AS
+$$
+BEGIN
+    IF TG_OP = 'DELETE' OR NEW.owner IS DISTINCT FROM OLD.owner THEN
+        INSERT INTO todo_removals (owner) VALUES (OLD.owner);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER todo_removals
+    AFTER UPDATE OF owner OR DELETE
+    ON todo_todos
+    FOR EACH ROW
+EXECUTE FUNCTION todo_record_removal();
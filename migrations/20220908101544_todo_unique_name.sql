@@ -0,0 +1,10 @@
+-- set on todos created with `?unique=true`; only those need names unique per owner,
+-- so the index is partial and everything else may keep its duplicates
+ALTER TABLE todo_todos
+    ADD COLUMN IF NOT EXISTS unique_name boolean NOT NULL DEFAULT false;
+
+CREATE UNIQUE INDEX IF NOT EXISTS todo_todos_unique_name
+    ON todo_todos ((coalesce(owner, '')), name) WHERE unique_name;
+
+ALTER TABLE todo_archive
+    ADD COLUMN IF NOT EXISTS unique_name boolean NOT NULL DEFAULT false;
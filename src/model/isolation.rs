@@ -0,0 +1,37 @@
+use crate::model::ConnectionPool;
+use sqlx::{Postgres, Transaction};
+
+/// How isolated a transaction is from concurrent ones, per Postgres'
+/// `SET TRANSACTION ISOLATION LEVEL`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum IsolationLevel {
+    /// Postgres' own default: each statement sees what was committed before it ran.
+    #[default]
+    ReadCommitted,
+    /// Every statement sees the snapshot the transaction's first one did.
+    RepeatableRead,
+    /// As if transactions ran one after another; conflicting ones are aborted with a
+    /// serialization failure, which [`crate::model::run_serializable_with_retry`] retries.
+    Serializable,
+}
+
+impl IsolationLevel {
+    fn sql(self) -> &'static str {
+        match self {
+            IsolationLevel::ReadCommitted => "set transaction isolation level read committed",
+            IsolationLevel::RepeatableRead => "set transaction isolation level repeatable read",
+            IsolationLevel::Serializable => "set transaction isolation level serializable",
+        }
+    }
+}
+
+/// Begins a transaction on `pool` at `level`; it has to be set before the transaction's
+/// first query, so do it here rather than after the fact.
+pub async fn begin_with_isolation(
+    pool: &ConnectionPool,
+    level: IsolationLevel,
+) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(level.sql()).execute(&mut tx).await?;
+    Ok(tx)
+}
@@ -0,0 +1,78 @@
+use crate::model::{begin_with_isolation, ConnectionPool, IsolationLevel};
+use futures::future::BoxFuture;
+use sqlx::{Postgres, Transaction};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// The SQLSTATE Postgres aborts a serializable transaction with when it conflicts
+/// with a concurrent one.
+const SERIALIZATION_FAILURE: &str = "40001";
+
+/// The most the first retry waits; each later one may wait up to twice as long.
+const BASE_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Runs `f` in a serializable transaction and commits it, starting over in a fresh
+/// transaction whenever Postgres aborts it with a serialization failure, `attempts`
+/// times in all. Retries wait a jittered, doubling backoff so that the writers that
+/// conflicted don't just collide again.
+///
+/// Any other error, and the last attempt's serialization failure, is returned as is.
+pub async fn run_serializable_with_retry<T, F>(
+    pool: &ConnectionPool,
+    mut f: F,
+    attempts: u32,
+) -> Result<T, sqlx::Error>
+where
+    F: for<'c> FnMut(
+        &'c mut Transaction<'static, Postgres>,
+    ) -> BoxFuture<'c, Result<T, sqlx::Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match run_serializable(pool, &mut f).await {
+            Err(err) if attempt < attempts && is_serialization_failure(&err) => {
+                let backoff = backoff(attempt);
+                log::debug!(
+                    "serialization failure on attempt {}, retrying in {:?}",
+                    attempt,
+                    backoff
+                );
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+async fn run_serializable<T, F>(pool: &ConnectionPool, f: &mut F) -> Result<T, sqlx::Error>
+where
+    F: for<'c> FnMut(
+        &'c mut Transaction<'static, Postgres>,
+    ) -> BoxFuture<'c, Result<T, sqlx::Error>>,
+{
+    let mut tx = begin_with_isolation(pool, IsolationLevel::Serializable).await?;
+    // an error drops the transaction, which rolls it back
+    let value = f(&mut tx).await?;
+    // conflicts can also surface only once the transaction commits
+    tx.commit().await?;
+    Ok(value)
+}
+
+/// Whether `err` is Postgres aborting a transaction that conflicted with a concurrent one,
+/// which trying again may well get past.
+pub(crate) fn is_serialization_failure(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Database(err) => err.code().as_deref() == Some(SERIALIZATION_FAILURE),
+        _ => false,
+    }
+}
+
+/// A random wait of up to `BASE_BACKOFF * 2^(attempt - 1)`.
+fn backoff(attempt: u32) -> Duration {
+    let ceiling = BASE_BACKOFF * 2u32.saturating_pow(attempt - 1);
+    // every `RandomState` is freshly seeded, which is all the randomness this needs
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_micros(random % (ceiling.as_micros() as u64 + 1))
+}
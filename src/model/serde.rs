@@ -1,13 +1,17 @@
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use serde::de::value::{MapDeserializer, SeqDeserializer};
 use serde::de::{DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
 use serde::Deserializer;
-use sqlx::postgres::{PgRow, PgTypeInfo, PgValueRef};
+use sqlx::postgres::{PgRow, PgValueFormat, PgValueRef};
+use sqlx::types::mac_address::MacAddress;
+use sqlx::types::{BitVec, Uuid};
 use sqlx::{Column, Decode, Row, TypeInfo, ValueRef};
-use std::borrow::Cow;
 
-use serde::de::value::SeqDeserializer;
 use sqlx::error::BoxDynError;
-use std::fmt::{Display, Formatter};
-use std::ops::Deref;
+use std::collections::HashMap;
+use std::fmt::Display;
 
 macro_rules! delegate_to_deserialize_any {
     ($($fn_name:ident), *) => {
@@ -24,6 +28,22 @@ macro_rules! delegate_to_deserialize_any {
 
 pub struct DbRow(pub PgRow);
 
+/// How a [`DbRow`] deserializes. The modes combine freely, e.g.
+/// `row.named().lenient().lossy_numeric()`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DbRowOptions {
+    /// See [`DbRow::lenient`].
+    pub lenient: bool,
+    /// See [`DbRow::null_defaults`].
+    pub null_defaults: bool,
+    /// See [`DbRow::lossy_numeric`].
+    pub numeric_as_f64: bool,
+    /// See [`DbRow::with_types`]; implies `named`.
+    pub with_types: bool,
+    /// See [`DbRow::named`].
+    pub named: bool,
+}
+
 impl<'de> IntoDeserializer<'de, Error> for DbRow {
     type Deserializer = DbRow;
     fn into_deserializer(self) -> Self::Deserializer {
@@ -31,14 +51,240 @@ impl<'de> IntoDeserializer<'de, Error> for DbRow {
     }
 }
 
+macro_rules! delegate_to_options {
+    ($($fn_name:ident), *) => {
+        $(
+            fn $fn_name<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.with_options(DbRowOptions::default()).$fn_name(visitor)
+            }
+        )*
+    };
+}
+
 impl<'de> Deserializer<'de> for DbRow {
     type Error = Error;
 
+    delegate_to_options! {
+        deserialize_any, deserialize_bool, deserialize_char,
+        deserialize_i8, deserialize_i16, deserialize_i32, deserialize_i64,
+        deserialize_u8, deserialize_u16, deserialize_u32, deserialize_u64,
+
+        deserialize_f32, deserialize_f64, deserialize_str, deserialize_string,
+        deserialize_unit, deserialize_bytes, deserialize_byte_buf, deserialize_identifier,
+        deserialize_option, deserialize_seq, deserialize_map, deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.with_options(DbRowOptions::default())
+            .deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.with_options(DbRowOptions::default())
+            .deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.with_options(DbRowOptions::default())
+            .deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.with_options(DbRowOptions::default())
+            .deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.with_options(DbRowOptions::default())
+            .deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.with_options(DbRowOptions::default())
+            .deserialize_enum(name, variants, visitor)
+    }
+}
+
+impl DbRow {
+    /// Deserialize according to `options` rather than the defaults.
+    pub fn with_options(self, options: DbRowOptions) -> DbRowWith {
+        DbRowWith { row: self, options }
+    }
+
+    /// Deserialize as a `{ column: value }` map even when the target would take anything
+    /// (`serde_json::Value`, a transcoder), instead of the default ordinal sequence.
+    pub fn named(self) -> DbRowWith {
+        self.with_options(DbRowOptions::default()).named()
+    }
+
+    /// Replace columns that fail to decode (unsupported types included) with `null`,
+    /// logging them, rather than failing the whole row.
+    pub fn lenient(self) -> DbRowWith {
+        self.with_options(DbRowOptions::default()).lenient()
+    }
+
+    /// Replace NULL integer, float, text and bool columns with `0`, `""` or `false`, for
+    /// clients that can't handle `null`; NULLs of other types stay `null`.
+    pub fn null_defaults(self) -> DbRowWith {
+        self.with_options(DbRowOptions::default()).null_defaults()
+    }
+
+    /// Decode `numeric` columns as `f64` numbers rather than exact decimal strings,
+    /// for clients happy to trade precision for convenience.
+    pub fn lossy_numeric(self) -> DbRowWith {
+        self.with_options(DbRowOptions::default()).lossy_numeric()
+    }
+
+    /// Like [`DbRow::named`], but as `{ column: { "value": ..., "type": ... } }` so
+    /// generic consumers can tell e.g. a timestamp from a plain string.
+    pub fn with_types(self) -> DbRowWith {
+        self.with_options(DbRowOptions::default()).with_types()
+    }
+
+    /// The row's `(column, value)` pairs in column order, which a map would lose.
+    pub fn into_pairs(self) -> Result<Vec<(String, serde_json::Value)>, Error> {
+        struct PairsVisitor;
+
+        impl<'de> Visitor<'de> for PairsVisitor {
+            type Value = Vec<(String, serde_json::Value)>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a row of named columns")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut pairs = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some(pair) = map.next_entry()? {
+                    pairs.push(pair);
+                }
+                Ok(pairs)
+            }
+        }
+
+        self.named().deserialize_map(PairsVisitor)
+    }
+}
+
+/// A [`DbRow`] deserialized according to its [`DbRowOptions`]; see [`DbRow::with_options`].
+pub struct DbRowWith {
+    row: DbRow,
+    options: DbRowOptions,
+}
+
+impl DbRowWith {
+    /// See [`DbRow::named`].
+    pub fn named(mut self) -> Self {
+        self.options.named = true;
+        self
+    }
+
+    /// See [`DbRow::lenient`].
+    pub fn lenient(mut self) -> Self {
+        self.options.lenient = true;
+        self
+    }
+
+    /// See [`DbRow::null_defaults`].
+    pub fn null_defaults(mut self) -> Self {
+        self.options.null_defaults = true;
+        self
+    }
+
+    /// See [`DbRow::lossy_numeric`].
+    pub fn lossy_numeric(mut self) -> Self {
+        self.options.numeric_as_f64 = true;
+        self
+    }
+
+    /// See [`DbRow::with_types`].
+    pub fn with_types(mut self) -> Self {
+        self.options.with_types = true;
+        self.options.named = true;
+        self
+    }
+
+    fn columns(&self) -> MapSeqqDeserializer<'_> {
+        MapSeqqDeserializer::new(&self.row, self.options)
+    }
+
+    fn expect_columns(&self, len: usize) -> Result<(), Error> {
+        if len != self.row.0.len() {
+            return Err(Error::custom(format_args!(
+                "expected a row of {} columns, found {}",
+                len,
+                self.row.0.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for DbRowWith {
+    type Deserializer = DbRowWith;
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for DbRowWith {
+    type Error = Error;
+
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.deserialize_seq(visitor)
+        if self.options.named {
+            self.deserialize_map(visitor)
+        } else {
+            self.deserialize_seq(visitor)
+        }
     }
 
     delegate_to_deserialize_any! {
@@ -83,19 +329,14 @@ impl<'de> Deserializer<'de> for DbRow {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_seq(MapSeqqDeserializer {
-            index: 0,
-            inner: &self,
-        })
+        visitor.visit_seq(self.columns())
     }
 
     fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        if len != self.0.len() {
-            return Err(Error::custom(""));
-        }
+        self.expect_columns(len)?;
         self.deserialize_seq(visitor)
     }
 
@@ -108,9 +349,7 @@ impl<'de> Deserializer<'de> for DbRow {
     where
         V: Visitor<'de>,
     {
-        if len != self.0.len() {
-            return Err(Error::custom(""));
-        }
+        self.expect_columns(len)?;
         self.deserialize_seq(visitor)
     }
 
@@ -118,10 +357,7 @@ impl<'de> Deserializer<'de> for DbRow {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_map(MapSeqqDeserializer {
-            index: 0,
-            inner: &self,
-        })
+        visitor.visit_map(self.columns())
     }
 
     fn deserialize_struct<V>(
@@ -133,10 +369,7 @@ impl<'de> Deserializer<'de> for DbRow {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_map(MapSeqqDeserializer {
-            index: 0,
-            inner: &self,
-        })
+        self.deserialize_map(visitor)
     }
 
     fn deserialize_enum<V>(
@@ -159,6 +392,60 @@ impl<'de> Deserializer<'de> for DbRow {
     }
 }
 
+/// A result set transcoded to a JSON array one row at a time, as rows arrive from the
+/// database, instead of collecting `Vec<DbRow>` first.
+///
+/// serde deserializers are synchronous and can't wait on the row stream, so rather than
+/// a `Deserializer` for the whole sequence this writes the array punctuation itself and
+/// transcodes each [`DbRow`] through its own deserializer.
+pub struct DbRowStream<'a> {
+    rows: BoxStream<'a, Result<DbRow, sqlx::Error>>,
+    started: bool,
+    finished: bool,
+}
+
+impl<'a> DbRowStream<'a> {
+    pub fn new(rows: BoxStream<'a, Result<DbRow, sqlx::Error>>) -> Self {
+        DbRowStream {
+            rows,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// The next piece of the JSON array, or `None` once the closing `]` has been produced.
+    pub async fn next_chunk(&mut self) -> Option<Result<Vec<u8>, Error>> {
+        if self.finished {
+            return None;
+        }
+
+        let mut chunk = vec![];
+        match self.rows.next().await {
+            Some(Ok(row)) => {
+                chunk.push(if self.started { b',' } else { b'[' });
+                self.started = true;
+                let mut json = serde_json::Serializer::new(&mut chunk);
+                if let Err(err) = serde_transcode::transcode(row, &mut json) {
+                    self.finished = true;
+                    return Some(Err(Error::custom(err)));
+                }
+            }
+            Some(Err(err)) => {
+                self.finished = true;
+                return Some(Err(err.into()));
+            }
+            None => {
+                if !self.started {
+                    chunk.push(b'[');
+                }
+                chunk.push(b']');
+                self.finished = true;
+            }
+        }
+        Some(Ok(chunk))
+    }
+}
+
 macro_rules! delegate_decode {
     ($($fn_name:ident|$visit_method:ident),*) => {
         $(
@@ -174,40 +461,226 @@ macro_rules! delegate_decode {
 
 pub struct DbColumn<'a> {
     column: PgValueRef<'a>,
+    /// See [`DbRow::lossy_numeric`].
+    numeric_as_f64: bool,
 }
 
-impl<'de: 'a, 'a> Deserializer<'de> for DbColumn<'a> {
+/// The single value of a one-column result (`select count(*) ...`), which deserializes
+/// as that bare value rather than as a one-element row.
+pub struct DbScalar<'a>(pub PgValueRef<'a>);
+
+impl<'a> DbScalar<'a> {
+    /// The first column of `row`.
+    pub fn new(row: &'a DbRow) -> Result<Self, Error> {
+        Ok(DbScalar(row.0.try_get_raw(0)?))
+    }
+}
+
+impl<'de: 'a, 'a> IntoDeserializer<'de, Error> for DbScalar<'a> {
+    type Deserializer = DbScalar<'a>;
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de: 'a, 'a> Deserializer<'de> for DbScalar<'a> {
     type Error = Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        // determine type information here: TODO
-        let kind = match self.column.type_info() {
-            Cow::Borrowed(ty) => Cow::Borrowed(ty.name()),
-            Cow::Owned(ty) => Cow::Owned(ty.to_string()),
+        DbColumn {
+            column: self.0,
+            numeric_as_f64: false,
+        }
+        .deserialize_any(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'a> DbColumn<'a> {
+    /// An integer column formatted as text, for targets that want a string from it
+    /// (clients modelling ids as strings); `None` for other column types.
+    fn integer_as_string(&self) -> Result<Option<String>, Error> {
+        let column = self.column.clone();
+        let formatted = match type_name(&column).as_str() {
+            "INT8" => i64::decode(column).map_err(Error::DecodeError)?.to_string(),
+            "INT4" => i32::decode(column).map_err(Error::DecodeError)?.to_string(),
+            "INT2" => i16::decode(column).map_err(Error::DecodeError)?.to_string(),
+            _ => return Ok(None),
         };
-        match kind.as_ref() {
+        Ok(Some(formatted))
+    }
+
+    /// The raw bytes of a `bytea` column; any other column type is an error rather
+    /// than its encoding reinterpreted as bytes.
+    fn bytea(&self) -> Result<&'a [u8], Error> {
+        match type_name(&self.column).as_str() {
+            "BYTEA" => Decode::decode(self.column.clone()).map_err(Error::DecodeError),
+            kind => Err(Error::custom(format_args!(
+                "can't deserialize bytes from column type {}",
+                kind
+            ))),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserializer<'de> for DbColumn<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // a NULL carries no value to decode, whatever the column type
+        if self.column.is_null() {
+            return visitor.visit_none();
+        }
+
+        let kind = type_name(&self.column);
+        match kind.as_str() {
             "INT8" => self.deserialize_i64(visitor),
             "INT4" => self.deserialize_i32(visitor),
             "INT2" => self.deserialize_i16(visitor),
-            "TEXT" | "VARCHAR" => self.deserialize_str(visitor),
+            "FLOAT8" => self.deserialize_f64(visitor),
+            "FLOAT4" => self.deserialize_f32(visitor),
+            // exact as a string unless the row asked for `f64`s
+            "NUMERIC" => {
+                let value = decode_numeric(self.column).map_err(Error::DecodeError)?;
+                if self.numeric_as_f64 {
+                    visitor.visit_f64(value.parse().map_err(Error::custom)?)
+                } else {
+                    visitor.visit_string(value)
+                }
+            }
+            // xml goes over the wire as its text in both formats
+            "TEXT" | "VARCHAR" | "CITEXT" | "XML" => self.deserialize_str(visitor),
             "BOOL" => self.deserialize_bool(visitor),
-            _ => {
-                unimplemented!()
+            // the catalogs' single-byte `"char"`, not `char(n)`; a zero byte is empty
+            "\"CHAR\"" => {
+                let value: i8 = Decode::decode(self.column).map_err(Error::DecodeError)?;
+                let value = match value as u8 {
+                    0 => String::new(),
+                    byte => char::from(byte).to_string(),
+                };
+                visitor.visit_string(value)
+            }
+            "BIT" | "VARBIT" => {
+                let value: BitVec = Decode::decode(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_string(
+                    value
+                        .iter()
+                        .map(|bit| if bit { '1' } else { '0' })
+                        .collect(),
+                )
             }
+            "HSTORE" => {
+                let value = decode_hstore(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_map(MapDeserializer::new(
+                    value
+                        .into_iter()
+                        .map(|(key, value)| (key, NullableString(value))),
+                ))
+            }
+            "POINT" => {
+                let (x, y) = decode_point(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_map(MapDeserializer::new([("x", x), ("y", y)].into_iter()))
+            }
+            "MACADDR" => {
+                let value: MacAddress = Decode::decode(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_string(value.to_string().to_ascii_lowercase())
+            }
+            "MACADDR8" => {
+                let value = decode_macaddr8(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_string(value)
+            }
+            "UUID" => {
+                let value: Uuid = Decode::decode(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_string(value.to_string())
+            }
+            "UUID[]" => {
+                let values: Vec<Uuid> = Decode::decode(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_seq(SeqDeserializer::new(
+                    values.into_iter().map(|value| value.to_string()),
+                ))
+            }
+            "DATE" => {
+                let value: NaiveDate = Decode::decode(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_string(value.to_string())
+            }
+            "JSON" | "JSONB" => {
+                let value: serde_json::Value =
+                    Decode::decode(self.column).map_err(Error::DecodeError)?;
+                value.deserialize_any(visitor).map_err(Error::custom)
+            }
+            "TIMESTAMPTZ" => {
+                let value: DateTime<Utc> =
+                    Decode::decode(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_string(value.to_rfc3339_opts(SecondsFormat::Micros, true))
+            }
+            _ => Err(Error::custom(format_args!(
+                "unsupported column type {}",
+                kind
+            ))),
         }
     }
 
     delegate_decode! {
-        deserialize_bool|visit_bool,
         deserialize_i8|visit_i8, deserialize_i16|visit_i16, deserialize_i32|visit_i32, deserialize_i64|visit_i64,
         deserialize_u8|visit_i8, deserialize_u16|visit_i16, deserialize_u32|visit_i32, deserialize_u64|visit_i64,
-        deserialize_f32|visit_f32, deserialize_f64|visit_f64, deserialize_str|visit_str, deserialize_string|visit_string,
+        deserialize_f32|visit_f32, deserialize_f64|visit_f64
+    }
 
-        deserialize_bytes|visit_bytes, deserialize_byte_buf|visit_byte_buf
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bytes(self.bytea()?)
+    }
 
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_byte_buf(self.bytea()?.to_vec())
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // some views hand booleans over as text
+        if let "TEXT" | "VARCHAR" | "CITEXT" = type_name(&self.column).as_str() {
+            let text: &str = Decode::decode(self.column).map_err(Error::DecodeError)?;
+            return visitor.visit_bool(parse_bool(text)?);
+        }
+        visitor.visit_bool(Decode::decode(self.column).map_err(Error::DecodeError)?)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(formatted) = self.integer_as_string()? {
+            return visitor.visit_string(formatted);
+        }
+        visitor.visit_str(Decode::decode(self.column).map_err(Error::DecodeError)?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(formatted) = self.integer_as_string()? {
+            return visitor.visit_string(formatted);
+        }
+        visitor.visit_string(Decode::decode(self.column).map_err(Error::DecodeError)?)
     }
 
     fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -262,81 +735,395 @@ impl<'de: 'a, 'a> Deserializer<'de> for DbColumn<'a> {
         visitor.visit_newtype_struct(self)
     }
 
-    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    // compound values (json, hstore, point) are all self-describing, so nested
+    // sequences and structs can be read off whatever `deserialize_any` produces
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        // a plain `Vec<u8>` asks for a sequence rather than for bytes
+        if type_name(&self.column) == "BYTEA" {
+            return visitor.visit_seq(SeqDeserializer::new(self.bytea()?.iter().copied()));
+        }
+        self.deserialize_any(visitor)
     }
 
-    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        self.deserialize_any(visitor)
     }
 
     fn deserialize_tuple_struct<V>(
         self,
         _name: &'static str,
         _len: usize,
-        _visitor: V,
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        self.deserialize_any(visitor)
     }
 
-    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        self.deserialize_any(visitor)
     }
 
     fn deserialize_struct<V>(
         self,
         _name: &'static str,
         _fields: &'static [&'static str],
-        _visitor: V,
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        self.deserialize_any(visitor)
     }
 
     fn deserialize_enum<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variants: &'static [&'static str],
-        _visitor: V,
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        // statuses kept as plain text name a unit variant
+        match type_name(&self.column).as_str() {
+            "TEXT" | "VARCHAR" | "CITEXT" => {
+                let text: &str = Decode::decode(self.column).map_err(Error::DecodeError)?;
+                visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(text))
+            }
+            kind => Err(Error::custom(format_args!(
+                "can't deserialize enum {} from column type {}",
+                name, kind
+            ))),
+        }
     }
 
-    fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    // a column naming a field or variant holds it as text
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        self.deserialize_str(visitor)
     }
 
-    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    // e.g. the columns of a `select *` that the target struct has no field for; whatever
+    // they hold, there's no need to decode it
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        visitor.visit_unit()
+    }
+}
+
+/// sqlx has no `hstore` support, so read its binary form by hand: a count followed by
+/// length-prefixed keys and values, where a value length of -1 means NULL.
+fn decode_hstore(column: PgValueRef<'_>) -> Result<HashMap<String, Option<String>>, BoxDynError> {
+    if column.format() != PgValueFormat::Binary {
+        return Err("hstore values can only be decoded from the binary format".into());
+    }
+    let mut buf = column.as_bytes()?;
+
+    fn read_i32(buf: &mut &[u8]) -> Result<i32, BoxDynError> {
+        let (int, rest) = buf.split_first_chunk().ok_or("truncated hstore value")?;
+        *buf = rest;
+        Ok(i32::from_be_bytes(*int))
+    }
+    fn read_str(buf: &mut &[u8], len: i32) -> Result<String, BoxDynError> {
+        let len = usize::try_from(len)?;
+        if buf.len() < len {
+            return Err("truncated hstore value".into());
+        }
+        let (text, rest) = buf.split_at(len);
+        *buf = rest;
+        Ok(std::str::from_utf8(text)?.to_owned())
+    }
+
+    let count = read_i32(&mut buf)?;
+    let mut map = HashMap::with_capacity(usize::try_from(count)?);
+    for _ in 0..count {
+        let len = read_i32(&mut buf)?;
+        let key = read_str(&mut buf, len)?;
+        let value = match read_i32(&mut buf)? {
+            -1 => None,
+            len => Some(read_str(&mut buf, len)?),
+        };
+        map.insert(key, value);
+    }
+    Ok(map)
+}
+
+/// sqlx 0.6 has no geometric types; a binary `point` is just two big-endian `float8`s.
+fn decode_point(column: PgValueRef<'_>) -> Result<(f64, f64), BoxDynError> {
+    if column.format() != PgValueFormat::Binary {
+        return Err("point values can only be decoded from the binary format".into());
+    }
+    let bytes: [u8; 16] = column
+        .as_bytes()?
+        .try_into()
+        .map_err(|_| "a point should be 16 bytes")?;
+    let (x, y) = bytes.split_at(8);
+    Ok((
+        f64::from_be_bytes(x.try_into()?),
+        f64::from_be_bytes(y.try_into()?),
+    ))
+}
+
+/// sqlx can only decode `numeric` through `bigdecimal` or `rust_decimal`, so render it
+/// the way Postgres prints it: base-10000 digit groups either side of the decimal
+/// point, padded or cut to the value's display scale.
+fn decode_numeric(column: PgValueRef<'_>) -> Result<String, BoxDynError> {
+    if column.format() != PgValueFormat::Binary {
+        return Err("numeric values can only be decoded from the binary format".into());
+    }
+    let bytes = column.as_bytes()?;
+    let word = |at: usize| -> Result<u16, BoxDynError> {
+        let word = bytes.get(at..at + 2).ok_or("a numeric was cut short")?;
+        Ok(u16::from_be_bytes(word.try_into()?))
+    };
+    let ndigits = word(0)? as usize;
+    let weight = word(2)? as i16 as i64;
+    let sign = word(4)?;
+    let scale = word(6)? as usize;
+    let digits = (0..ndigits)
+        .map(|i| word(8 + 2 * i))
+        .collect::<Result<Vec<_>, _>>()?;
+    // the group `weight - i` places after the first one, or 0 past either end
+    let group = |i: i64| {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| digits.get(i).copied())
+            .unwrap_or(0)
+    };
+
+    let mut text = match sign {
+        0x0000 => String::new(),
+        0x4000 => "-".to_string(),
+        0xC000 => return Ok("NaN".into()),
+        0xD000 => return Ok("Infinity".into()),
+        0xF000 => return Ok("-Infinity".into()),
+        sign => return Err(format!("unknown numeric sign {:#x}", sign).into()),
+    };
+    if weight < 0 {
+        text.push('0');
+    } else {
+        text.push_str(&group(0).to_string());
+        for i in 1..=weight {
+            text.push_str(&format!("{:04}", group(i)));
+        }
+    }
+    if scale > 0 {
+        let mut fraction = String::new();
+        let mut i = weight + 1;
+        while fraction.len() < scale {
+            fraction.push_str(&format!("{:04}", group(i)));
+            i += 1;
+        }
+        fraction.truncate(scale);
+        text.push('.');
+        text.push_str(&fraction);
+    }
+    Ok(text)
+}
+
+/// `MacAddress` only holds the 6-byte `macaddr`, so render `macaddr8` ourselves the way
+/// Postgres does: eight lowercase hex octets separated by colons.
+fn decode_macaddr8(column: PgValueRef<'_>) -> Result<String, BoxDynError> {
+    if column.format() != PgValueFormat::Binary {
+        return Err("macaddr8 values can only be decoded from the binary format".into());
+    }
+    let bytes: [u8; 8] = column
+        .as_bytes()?
+        .try_into()
+        .map_err(|_| "a macaddr8 should be 8 bytes")?;
+    Ok(bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+/// The textual booleans Postgres itself accepts for `bool` input, case-insensitively.
+fn parse_bool(text: &str) -> Result<bool, Error> {
+    match text.trim().to_ascii_lowercase().as_str() {
+        "t" | "true" | "y" | "yes" | "on" | "1" => Ok(true),
+        "f" | "false" | "n" | "no" | "off" | "0" => Ok(false),
+        _ => Err(Error::custom(format_args!("{:?} is not a boolean", text))),
+    }
+}
+
+/// The column's type name as matched on by [`DbColumn`].
+fn type_name(column: &PgValueRef) -> String {
+    // built-in types come back upper-cased but extension types (citext, ...) are
+    // reported by their `pg_type.typname`, so normalize
+    column.type_info().name().to_ascii_uppercase()
+}
+
+/// One column of a [`DbRow::with_types`] row: a `{ "value": ..., "type": ... }` map.
+struct TypedColumn<'a> {
+    name: &'a str,
+    column: Option<PgValueRef<'a>>,
+    type_name: Option<String>,
+    options: DbRowOptions,
+}
+
+impl<'de: 'a, 'a> Deserializer<'de> for TypedColumn<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de: 'a, 'a> MapAccess<'de> for TypedColumn<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let key = if self.column.is_some() {
+            "value"
+        } else if self.type_name.is_some() {
+            "type"
+        } else {
+            return Ok(None);
+        };
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match (self.column.take(), self.type_name.take()) {
+            (Some(column), type_name) => {
+                self.type_name = type_name;
+                deserialize_column(self.name, column, self.options, seed)
+            }
+            (None, Some(type_name)) => seed.deserialize(type_name.into_deserializer()),
+            (None, None) => Err(Error::custom("no more values in the column")),
+        }
+    }
+}
+
+/// A possibly-NULL text value that deserializes as an option.
+struct NullableString(Option<String>);
+
+impl<'de> IntoDeserializer<'de, Error> for NullableString {
+    type Deserializer = Self;
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for NullableString {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Some(value) => visitor.visit_string(value),
+            None => visitor.visit_none(),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Some(value) => visitor.visit_some(value.into_deserializer()),
+            None => visitor.visit_none(),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
     }
 }
 
 pub struct MapSeqqDeserializer<'a> {
     inner: &'a DbRow,
     index: usize,
+    options: DbRowOptions,
+}
+
+impl<'a> MapSeqqDeserializer<'a> {
+    fn new(inner: &'a DbRow, options: DbRowOptions) -> Self {
+        MapSeqqDeserializer {
+            inner,
+            index: 0,
+            options,
+        }
+    }
+}
+
+/// Hands the column `name` to `seed` as `options` say. When lenient, a column that
+/// fails to decode is logged and passed on as `null` instead of failing the row.
+fn deserialize_column<'de: 'a, 'a, T>(
+    name: &str,
+    column: PgValueRef<'a>,
+    options: DbRowOptions,
+    seed: T,
+) -> Result<T::Value, Error>
+where
+    T: DeserializeSeed<'de>,
+{
+    if options.null_defaults && column.is_null() {
+        if let Some(default) = null_default(&type_name(&column)) {
+            return seed.deserialize(default).map_err(Error::custom);
+        }
+    }
+
+    let column = DbColumn {
+        column,
+        numeric_as_f64: options.numeric_as_f64,
+    };
+    if !options.lenient {
+        return seed.deserialize(column);
+    }
+
+    // decode into a value first: once `seed` has failed it can't be given a null
+    let value = serde::Deserialize::deserialize(column).unwrap_or_else(|err| {
+        log::warn!("replacing column {:?} with null: {}", name, err);
+        serde_json::Value::Null
+    });
+    seed.deserialize(value).map_err(Error::custom)
+}
+
+/// What [`DbRow::null_defaults`] puts in place of a NULL of type `kind`.
+fn null_default(kind: &str) -> Option<serde_json::Value> {
+    match kind {
+        "INT8" | "INT4" | "INT2" => Some(0.into()),
+        "FLOAT8" | "FLOAT4" => Some(0.0.into()),
+        "TEXT" | "VARCHAR" | "CITEXT" => Some("".into()),
+        "BOOL" => Some(false.into()),
+        _ => None,
+    }
 }
 
 impl<'de: 'a, 'a> SeqAccess<'de> for MapSeqqDeserializer<'a> {
@@ -349,11 +1136,10 @@ impl<'de: 'a, 'a> SeqAccess<'de> for MapSeqqDeserializer<'a> {
         if self.index >= self.inner.0.len() {
             return Ok(None);
         }
+        let name = self.inner.0.column(self.index).name();
         let column = self.inner.0.try_get_raw(self.index)?;
         self.index += 1;
-        let column_deserializer = DbColumn { column };
-
-        T::deserialize(seed, column_deserializer).map(Some)
+        deserialize_column(name, column, self.options, seed).map(Some)
     }
 }
 
@@ -377,12 +1163,24 @@ impl<'de: 'a, 'a> MapAccess<'de> for MapSeqqDeserializer<'a> {
     where
         V: DeserializeSeed<'de>,
     {
+        let name = self.inner.0.column(self.index).name();
         let column = self.inner.0.try_get_raw(self.index)?;
         self.index += 1;
-        seed.deserialize(DbColumn { column })
+        if self.options.with_types {
+            let type_name = Some(type_name(&column));
+            seed.deserialize(TypedColumn {
+                name,
+                column: Some(column),
+                type_name,
+                options: self.options,
+            })
+        } else {
+            deserialize_column(name, column, self.options, seed)
+        }
     }
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("DB Deserialization error: {0}")]
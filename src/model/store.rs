@@ -0,0 +1,142 @@
+use crate::model::serde::DbRow;
+use crate::model::timing::timed;
+use crate::model::{normalize_name, Todo};
+use sqlx::PgConnection;
+
+/// [`Todo`]'s queries against a table of your choosing, for serving several tables with
+/// the same shape as `todo_todos` from one codebase.
+///
+/// `query!` needs the table name at compile time, so these go through the runtime query
+/// API instead and lose the compile-time checks; prefer the [`Todo`] methods for
+/// `todo_todos` itself.
+#[derive(Clone, Debug)]
+pub struct TodoStore {
+    table: String,
+}
+
+impl TodoStore {
+    /// Fails unless `table` is a plain lowercase identifier (optionally schema-qualified),
+    /// since it's spliced into the SQL.
+    pub fn new(table: impl Into<String>) -> Result<Self, sqlx::Error> {
+        let table = table.into();
+        let valid = table.split('.').count() <= 2
+            && table.split('.').all(|part| {
+                part.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+                    && part
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            });
+        if !valid {
+            return Err(sqlx::Error::Configuration(
+                format!("invalid todo table name {:?}", table).into(),
+            ));
+        }
+        Ok(TodoStore { table })
+    }
+
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    pub async fn create_todo(
+        &self,
+        connection: &mut PgConnection,
+        name: impl AsRef<str>,
+        done: bool,
+    ) -> Result<Todo, sqlx::Error> {
+        timed(
+            "TodoStore::create_todo",
+            sqlx::query_as(&format!(
+                "insert into {} (name, done) values ($1, $2) returning *",
+                self.table
+            ))
+            .bind(normalize_name(name.as_ref()))
+            .bind(done)
+            .fetch_one(connection),
+        )
+        .await
+    }
+
+    /// See [`Todo::update_todo`].
+    pub async fn update_todo(
+        &self,
+        connection: &mut PgConnection,
+        id: i32,
+        name: Option<&str>,
+        done: Option<bool>,
+    ) -> Result<Todo, sqlx::Error> {
+        timed(
+            "TodoStore::update_todo",
+            sqlx::query_as(&format!(
+                "update {} set name = coalesce($2, name), done = coalesce($3, done), updated_at = now()
+                 where id = $1 returning *",
+                self.table
+            ))
+            .bind(id)
+            .bind(name.map(normalize_name))
+            .bind(done)
+            .fetch_one(connection),
+        )
+        .await
+    }
+
+    pub async fn get_todo(
+        &self,
+        connection: &mut PgConnection,
+        id: i32,
+    ) -> Result<Todo, sqlx::Error> {
+        timed(
+            "TodoStore::get_todo",
+            sqlx::query_as(&format!("select * from {} where id = $1", self.table))
+                .bind(id)
+                .fetch_one(connection),
+        )
+        .await
+    }
+
+    pub async fn get_all_todos(
+        &self,
+        connection: &mut PgConnection,
+    ) -> Result<Vec<Todo>, sqlx::Error> {
+        timed(
+            "TodoStore::get_all_todos",
+            sqlx::query_as(&format!("select * from {}", self.table)).fetch_all(connection),
+        )
+        .await
+    }
+
+    /// See [`Todo::search_todos`]; this one has no limit.
+    pub async fn search_todos(
+        &self,
+        connection: &mut PgConnection,
+        search: &str,
+    ) -> Result<Vec<DbRow>, sqlx::Error> {
+        timed(
+            "TodoStore::search_todos",
+            sqlx::query(&format!(
+                "select * from {} where name like $1 order by id",
+                self.table
+            ))
+            .bind(format!("%{}%", search))
+            .map(DbRow)
+            .fetch_all(connection),
+        )
+        .await
+    }
+
+    pub async fn delete_todo(
+        &self,
+        connection: &mut PgConnection,
+        id: i32,
+    ) -> Result<u64, sqlx::Error> {
+        let deleted = timed(
+            "TodoStore::delete_todo",
+            sqlx::query(&format!("delete from {} where id = $1", self.table))
+                .bind(id)
+                .execute(connection),
+        )
+        .await?
+        .rows_affected();
+        Ok(deleted)
+    }
+}
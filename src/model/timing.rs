@@ -0,0 +1,41 @@
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+const DEFAULT_SLOW_QUERY: Duration = Duration::from_millis(500);
+
+static THRESHOLD: OnceLock<Duration> = OnceLock::new();
+
+/// Sets how long a model query may take before it's logged as slow, e.g. from
+/// `SLOW_QUERY_MS`. Only the first call counts, and only before any query has run.
+pub fn set_slow_query_threshold(threshold: Duration) {
+    let _ = THRESHOLD.set(threshold);
+}
+
+/// How long a model query may take before it's logged as slow: whatever
+/// [`set_slow_query_threshold`] set, or half a second.
+pub fn slow_query_threshold() -> Duration {
+    *THRESHOLD.get_or_init(|| DEFAULT_SLOW_QUERY)
+}
+
+/// Awaits `query`, warning if it took longer than [`slow_query_threshold`].
+pub(crate) async fn timed<F: Future>(method: &str, query: F) -> F::Output {
+    timed_against(slow_query_threshold(), method, query).await.0
+}
+
+/// Awaits `query`, warning with `method`'s name if it took longer than `threshold`;
+/// also says whether it did.
+pub(crate) async fn timed_against<F: Future>(
+    threshold: Duration,
+    method: &str,
+    query: F,
+) -> (F::Output, bool) {
+    let start = Instant::now();
+    let output = query.await;
+    let elapsed = start.elapsed();
+    let slow = elapsed > threshold;
+    if slow {
+        log::warn!("slow query in {}: took {:?}", method, elapsed);
+    }
+    (output, slow)
+}
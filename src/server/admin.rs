@@ -0,0 +1,45 @@
+use crate::model::Todo;
+use crate::server::{ApiError, Db, Json};
+use actix_web::web::{self, ServiceConfig};
+use actix_web::{HttpRequest, Responder};
+
+/// The shared secret admin routes expect in `X-Admin-Token`. Without one registered
+/// every admin request is refused.
+#[derive(Clone, Debug)]
+pub struct AdminToken(pub String);
+
+pub fn admin_service(config: &mut ServiceConfig) {
+    config.service(web::scope("/admin").route("/reset", web::post().to(reset)));
+}
+
+fn authorize(req: &HttpRequest) -> Result<(), ApiError> {
+    let expected = req.app_data::<web::Data<AdminToken>>();
+    let given = req.headers().get("X-Admin-Token");
+    match (expected, given) {
+        (Some(expected), Some(given)) if tokens_match(given.as_bytes(), expected.0.as_bytes()) => {
+            Ok(())
+        }
+        _ => Err(ApiError::Forbidden),
+    }
+}
+
+/// Whether `given` is `expected`, in the same time wherever they first differ so the
+/// token can't be guessed a byte at a time. Only its length can leak.
+fn tokens_match(given: &[u8], expected: &[u8]) -> bool {
+    if given.len() != expected.len() {
+        return false;
+    }
+    let differences = given
+        .iter()
+        .zip(expected)
+        .fold(0, |differences, (given, expected)| {
+            differences | (given ^ expected)
+        });
+    differences == 0
+}
+
+async fn reset(req: HttpRequest, Db(mut conn): Db) -> Result<impl Responder, ApiError> {
+    authorize(&req)?;
+    Todo::truncate(&mut conn).await?;
+    Ok(Json(serde_json::json!({ "reset": true })))
+}
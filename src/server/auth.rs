@@ -0,0 +1,148 @@
+use crate::server::ApiError;
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header;
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpMessage, HttpRequest};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// The keys accepted as `Authorization: Bearer <key>` on the API.
+///
+/// Authentication is off while neither these nor a [`JwtConfig`] are registered with
+/// the app.
+#[derive(Clone, Debug, Default)]
+pub struct ApiKeys(pub HashSet<String>);
+
+impl ApiKeys {
+    /// Parses a comma-separated list, ignoring blanks.
+    pub fn parse(keys: &str) -> Self {
+        ApiKeys(
+            keys.split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(str::to_owned)
+                .collect(),
+        )
+    }
+}
+
+/// Verifies bearer JWTs signed with a shared secret; their `sub` claim becomes the
+/// request's [`User`].
+#[derive(Clone)]
+pub struct JwtConfig {
+    key: DecodingKey,
+    validation: Validation,
+}
+
+#[derive(serde::Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+impl JwtConfig {
+    /// For the HMAC algorithms (`HS256` and friends); tokens must carry an `exp`.
+    pub fn hmac(secret: &[u8], algorithm: Algorithm) -> Self {
+        JwtConfig {
+            key: DecodingKey::from_secret(secret),
+            validation: Validation::new(algorithm),
+        }
+    }
+
+    fn user(&self, token: &str) -> Option<User> {
+        match jsonwebtoken::decode::<Claims>(token, &self.key, &self.validation) {
+            Ok(token) => Some(User(token.claims.sub)),
+            Err(err) => {
+                log::debug!("rejected JWT: {}", err);
+                None
+            }
+        }
+    }
+}
+
+/// The user a verified JWT was issued to, for handlers behind [`Authenticate`].
+///
+/// Extracting it fails with [`ApiError::Unauthorized`] when the request didn't carry
+/// a JWT (API keys don't identify anyone).
+#[derive(Clone, Debug, PartialEq)]
+pub struct User(pub String);
+
+impl FromRequest for User {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<User>()
+                .cloned()
+                .ok_or(ApiError::Unauthorized),
+        )
+    }
+}
+
+/// Middleware refusing requests with [`ApiError::Unauthorized`] unless their bearer
+/// token is one of the [`ApiKeys`] or a JWT accepted by the [`JwtConfig`], whichever
+/// are registered. A JWT's [`User`] is stored in the request extensions.
+pub struct Authenticate;
+
+impl<S, B> Transform<S, ServiceRequest> for Authenticate
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = AuthenticateMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuthenticateMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct AuthenticateMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for AuthenticateMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let keys = req.app_data::<Data<ApiKeys>>();
+        let jwt = req.app_data::<Data<JwtConfig>>();
+        if keys.is_some() || jwt.is_some() {
+            let token = req
+                .headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|authorization| authorization.to_str().ok())
+                .and_then(|authorization| authorization.strip_prefix("Bearer "));
+            let is_key = token.is_some_and(|token| keys.is_some_and(|keys| keys.0.contains(token)));
+            let user = match (token, jwt) {
+                (Some(token), Some(jwt)) if !is_key => jwt.user(token),
+                _ => None,
+            };
+            if !is_key && user.is_none() {
+                return Box::pin(ready(Err(ApiError::Unauthorized.into())));
+            }
+            if let Some(user) = user {
+                req.extensions_mut().insert(user);
+            }
+        }
+
+        let service = self.service.clone();
+        Box::pin(async move { service.call(req).await })
+    }
+}
@@ -0,0 +1,142 @@
+use actix_web::body::{self, BoxBody, MessageBody};
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::PayloadError;
+use actix_web::http::header::{self, HeaderMap};
+use actix_web::web::{Bytes, Data};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use futures::Stream;
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::rc::Rc;
+
+type PayloadStream = Pin<Box<dyn Stream<Item = Result<Bytes, PayloadError>>>>;
+
+const REDACTED: &str = "[redacted]";
+
+/// Turns on [`LogBodies`] when registered with the app: JSON request and response
+/// bodies are logged at debug level, with the values of any `redact`ed fields
+/// replaced, wherever they're nested.
+///
+/// Meant for local debugging only; bodies are buffered in full to log them.
+#[derive(Clone, Debug, Default)]
+pub struct BodyLogging {
+    pub redact: HashSet<String>,
+}
+
+impl BodyLogging {
+    /// Redacting the fields in a comma-separated list, e.g. `password,token`.
+    pub fn redacting(fields: &str) -> Self {
+        BodyLogging {
+            redact: fields
+                .split(',')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .map(str::to_owned)
+                .collect(),
+        }
+    }
+
+    fn log(&self, what: &str, body: &Bytes) {
+        let Ok(mut json) = serde_json::from_slice::<serde_json::Value>(body) else {
+            return;
+        };
+        self.redact(&mut json);
+        log::debug!("{}: {}", what, json);
+    }
+
+    fn redact(&self, json: &mut serde_json::Value) {
+        match json {
+            serde_json::Value::Object(fields) => {
+                for (name, value) in fields {
+                    if self.redact.contains(name) {
+                        *value = REDACTED.into();
+                    } else {
+                        self.redact(value);
+                    }
+                }
+            }
+            serde_json::Value::Array(values) => {
+                values.iter_mut().for_each(|value| self.redact(value))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether a body is JSON, and so finite and worth buffering; event streams aren't.
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.contains("json"))
+}
+
+/// Middleware logging JSON bodies per the app's [`BodyLogging`]; a no-op without one.
+pub struct LogBodies;
+
+impl<S, B> Transform<S, ServiceRequest> for LogBodies
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = actix_web::Error;
+    type Transform = LogBodiesMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(LogBodiesMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct LogBodiesMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for LogBodiesMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        Box::pin(async move {
+            let Some(logging) = req.app_data::<Data<BodyLogging>>().cloned() else {
+                return Ok(service.call(req).await?.map_into_boxed_body());
+            };
+
+            if is_json(req.headers()) {
+                let body = req.extract::<Bytes>().await?;
+                logging.log(&format!("{} {} request", req.method(), req.path()), &body);
+                // put the body back for the handler to read
+                let replay: PayloadStream = Box::pin(futures::stream::once(ready(Ok(body))));
+                req.set_payload(Payload::from(replay));
+            }
+
+            let res = service.call(req).await?;
+            if !is_json(res.headers()) {
+                return Ok(res.map_into_boxed_body());
+            }
+            let (req, res) = res.into_parts();
+            let (res, body) = res.into_parts();
+            let body = body::to_bytes(body).await.map_err(|err| {
+                let err: Box<dyn std::error::Error> = err.into();
+                actix_web::error::ErrorInternalServerError(err.to_string())
+            })?;
+            logging.log(&format!("{} {} response", req.method(), req.path()), &body);
+            Ok(ServiceResponse::new(
+                req,
+                res.set_body(body).map_into_boxed_body(),
+            ))
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use actix_web::http::header::{CacheControl, CacheDirective, Header, TryIntoHeaderValue};
+use actix_web::web::Data;
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use std::time::Duration;
+
+/// How long clients may reuse [`Cached`] responses; five seconds unless registered
+/// with the app.
+#[derive(Clone, Copy, Debug)]
+pub struct CacheMaxAge(pub Duration);
+
+impl Default for CacheMaxAge {
+    fn default() -> Self {
+        CacheMaxAge(Duration::from_secs(5))
+    }
+}
+
+/// Wraps a responder for endpoints that tolerate brief staleness, marking its
+/// successful responses `Cache-Control: max-age=N` with the app's [`CacheMaxAge`].
+pub struct Cached<R>(pub R);
+
+impl<R: Responder> Responder for Cached<R> {
+    type Body = R::Body;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let max_age = req
+            .app_data::<Data<CacheMaxAge>>()
+            .map(|max_age| *max_age.get_ref())
+            .unwrap_or_default();
+        let mut res = self.0.respond_to(req);
+        let secs = max_age.0.as_secs().try_into().unwrap_or(u32::MAX);
+        let cache_control = CacheControl(vec![CacheDirective::MaxAge(secs)]);
+        if let (true, Ok(value)) = (res.status().is_success(), cache_control.try_into_value()) {
+            res.headers_mut().insert(CacheControl::name(), value);
+        }
+        res
+    }
+}
@@ -0,0 +1,92 @@
+use crate::model::ConnectionPool;
+use crate::server::ApiError;
+use actix_web::dev::Payload;
+use actix_web::http::Method;
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+use sqlx::pool::PoolConnection;
+use sqlx::Postgres;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The primary pool and an optional read replica. Registered with the app, [`Db`]
+/// hands `GET` and `HEAD` requests a replica connection and everything else a primary
+/// one; without a replica every request uses the primary.
+///
+/// The app's plain [`ConnectionPool`] is still used by everything besides [`Db`] and
+/// [`Primary`] (transactions, health checks), so register the primary as that too.
+#[derive(Clone, Debug)]
+pub struct Pools {
+    pub primary: ConnectionPool,
+    pub replica: Option<ConnectionPool>,
+}
+
+impl Pools {
+    /// The pool a request with `method` should read from or write to.
+    pub fn for_method(&self, method: &Method) -> &ConnectionPool {
+        match &self.replica {
+            Some(replica) if matches!(*method, Method::GET | Method::HEAD) => replica,
+            _ => &self.primary,
+        }
+    }
+
+    /// Closes both pools for shutdown, waiting for connections still checked out to be
+    /// returned so the database sees them disconnect cleanly.
+    pub async fn close(&self) {
+        if let Some(replica) = &self.replica {
+            replica.close().await;
+        }
+        self.primary.close().await;
+    }
+}
+
+/// A connection checked out of the app's [`ConnectionPool`] for the duration of a handler,
+/// or out of [`Pools`] when those are registered.
+///
+/// Failing to acquire one (including a missing pool) is reported as [`ApiError::Database`].
+pub struct Db(pub PoolConnection<Postgres>);
+
+impl FromRequest for Db {
+    type Error = ApiError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let pool = registered_pool(req, |pools| pools.for_method(req.method()));
+        Box::pin(async move {
+            let conn = pool?.acquire().await.map_err(ApiError::Database)?;
+            Ok(Db(conn))
+        })
+    }
+}
+
+/// The app's primary pool itself, for handlers that run their own transactions on it;
+/// found like [`Db`]'s connections, but never the replica.
+pub struct Primary(pub ConnectionPool);
+
+impl FromRequest for Primary {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(registered_pool(req, |pools| &pools.primary).map(Primary))
+    }
+}
+
+/// `pick`'s pool out of the app's [`Pools`], or else its plain [`ConnectionPool`].
+fn registered_pool(
+    req: &HttpRequest,
+    pick: impl FnOnce(&Pools) -> &ConnectionPool,
+) -> Result<ConnectionPool, ApiError> {
+    let pool = match req.app_data::<Data<Pools>>() {
+        Some(pools) => Some(pick(pools).clone()),
+        None => req
+            .app_data::<Data<ConnectionPool>>()
+            .map(|pool| pool.get_ref().clone()),
+    };
+    pool.ok_or_else(|| {
+        ApiError::Database(sqlx::Error::Configuration(
+            "no connection pool registered with the app".into(),
+        ))
+    })
+}
@@ -0,0 +1,111 @@
+use crate::model::{is_serialization_failure, UNIQUE_VIOLATION};
+use actix_web::http::{header, StatusCode};
+use actix_web::{HttpResponse, ResponseError};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Field-level validation failures, rendered as `{ "errors": { field: message } }`.
+#[derive(Debug, Default, Serialize)]
+pub struct ValidationErrors(BTreeMap<&'static str, String>);
+
+impl ValidationErrors {
+    pub fn add(&mut self, field: &'static str, message: impl Into<String>) {
+        self.0.insert(field, message.into());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// `Ok` when nothing was added, otherwise the errors as an [`ApiError::Validation`].
+    pub fn into_result(self) -> Result<(), ApiError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::Validation(self))
+        }
+    }
+}
+
+/// How long clients are told to back off when no connection could be acquired.
+const RETRY_AFTER_SECS: u32 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("missing or invalid credentials")]
+    Unauthorized,
+
+    #[error("forbidden")]
+    Forbidden,
+
+    #[error("not found")]
+    NotFound,
+
+    #[error("validation failed")]
+    Validation(ValidationErrors),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("request timed out")]
+    Timeout,
+
+    #[error("too busy, try again shortly")]
+    Busy,
+
+    #[error("database error: {0}")]
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => ApiError::NotFound,
+            error if is_serialization_failure(&error) => {
+                ApiError::Conflict("conflicted with a concurrent change, try again".into())
+            }
+            sqlx::Error::Database(error) if error.code().as_deref() == Some(UNIQUE_VIOLATION) => {
+                ApiError::Conflict("conflicts with an existing todo".into())
+            }
+            error => ApiError::Database(error),
+        }
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden => StatusCode::FORBIDDEN,
+            ApiError::NotFound => StatusCode::NOT_FOUND,
+            ApiError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            ApiError::Busy => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::Database(sqlx::Error::PoolTimedOut) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let body = match self {
+            ApiError::Validation(errors) => serde_json::json!({ "errors": errors }),
+            error => serde_json::json!({ "error": error.to_string() }),
+        };
+        let mut response = HttpResponse::build(self.status_code());
+        match self {
+            ApiError::Busy | ApiError::Database(sqlx::Error::PoolTimedOut) => {
+                response.insert_header((header::RETRY_AFTER, RETRY_AFTER_SECS));
+            }
+            ApiError::Unauthorized => {
+                response.insert_header((header::WWW_AUTHENTICATE, "Bearer"));
+            }
+            _ => {}
+        }
+        response.json(body)
+    }
+}
@@ -0,0 +1,161 @@
+use crate::model::{ConnectionPool, Todo};
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::web::{self, Bytes};
+use actix_web::HttpResponse;
+use sqlx::postgres::PgListener;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+#[derive(serde::Serialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TodoEvent {
+    Created { todo: Todo },
+    Updated { todo: Todo },
+    Deleted { id: i32 },
+}
+
+/// The channel the `todo_changes` trigger notifies on.
+pub const CHANGE_CHANNEL: &str = "todo_changes";
+
+#[derive(serde::Deserialize)]
+struct Change {
+    op: String,
+    id: i32,
+}
+
+/// Fans todo changes out to every `/todos/events` subscriber.
+///
+/// Changes come from Postgres (see [`ChangeFeed`]) so writes made by any instance
+/// are seen; create one per process and share it between workers.
+#[derive(Clone, Debug)]
+pub struct TodoEvents(broadcast::Sender<TodoEvent>);
+
+impl TodoEvents {
+    pub fn new(capacity: usize) -> Self {
+        TodoEvents(broadcast::channel(capacity).0)
+    }
+
+    pub fn publish(&self, event: TodoEvent) {
+        // nobody listening isn't an error
+        let _ = self.0.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<TodoEvent> {
+        self.0.subscribe()
+    }
+}
+
+impl Default for TodoEvents {
+    fn default() -> Self {
+        TodoEvents::new(64)
+    }
+}
+
+/// How long [`ChangeFeed::forward`] waits before reconnecting a failed listener.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Relays `todo_changes` notifications from Postgres into [`TodoEvents`].
+pub struct ChangeFeed(PgListener);
+
+impl ChangeFeed {
+    /// Subscribes `listener` to the change channel; anything committed after this
+    /// returns will be seen by [`ChangeFeed::forward`].
+    pub async fn listen(mut listener: PgListener) -> Result<Self, sqlx::Error> {
+        listener.listen(CHANGE_CHANNEL).await?;
+        Ok(ChangeFeed(listener))
+    }
+
+    /// Publishes every change to `events`, for as long as the process runs. A failed
+    /// listener is logged and replaced with a fresh one on `pool`, and a change whose row
+    /// can't be looked up is logged and skipped.
+    ///
+    /// The trigger only sends ids, so created and updated rows are looked up again; a
+    /// row that's gone by then has a delete notification of its own on the way.
+    pub async fn forward(mut self, pool: ConnectionPool, events: TodoEvents) {
+        loop {
+            let notification = match self.0.recv().await {
+                Ok(notification) => notification,
+                Err(err) => {
+                    log::warn!("change feed failed, reconnecting: {}", err);
+                    self = ChangeFeed::reconnect(&pool).await;
+                    continue;
+                }
+            };
+            let change: Change = match serde_json::from_str(notification.payload()) {
+                Ok(change) => change,
+                Err(err) => {
+                    log::warn!(
+                        "unexpected change payload {:?}: {}",
+                        notification.payload(),
+                        err
+                    );
+                    continue;
+                }
+            };
+
+            let event = match change.op.as_str() {
+                "delete" => TodoEvent::Deleted { id: change.id },
+                op => {
+                    let todo = match pool.acquire().await {
+                        Ok(mut conn) => Todo::get_todo(&mut conn, change.id).await,
+                        Err(err) => Err(err),
+                    };
+                    let todo = match todo {
+                        Ok(todo) => todo,
+                        Err(sqlx::Error::RowNotFound) => continue,
+                        Err(err) => {
+                            log::warn!("looking up changed todo {} failed: {}", change.id, err);
+                            continue;
+                        }
+                    };
+                    if op == "insert" {
+                        TodoEvent::Created { todo }
+                    } else {
+                        TodoEvent::Updated { todo }
+                    }
+                }
+            };
+            events.publish(event);
+        }
+    }
+
+    /// Listens again on a new connection, retrying every [`RECONNECT_DELAY`] until it can.
+    async fn reconnect(pool: &ConnectionPool) -> Self {
+        loop {
+            actix_web::rt::time::sleep(RECONNECT_DELAY).await;
+            let feed = match PgListener::connect_with(pool).await {
+                Ok(listener) => ChangeFeed::listen(listener).await,
+                Err(err) => Err(err),
+            };
+            match feed {
+                Ok(feed) => return feed,
+                Err(err) => log::warn!("reconnecting the change feed failed: {}", err),
+            }
+        }
+    }
+}
+
+pub async fn todo_events(events: web::Data<TodoEvents>) -> HttpResponse {
+    let body = futures::stream::unfold(events.subscribe(), |mut events| async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    let json = serde_json::to_string(&event).expect("events serialize");
+                    let chunk = Bytes::from(format!("data: {}\n\n", json));
+                    return Some((Ok::<_, actix_web::Error>(chunk), events));
+                }
+                // a slow subscriber just misses what it couldn't keep up with
+                Err(RecvError::Lagged(missed)) => {
+                    log::warn!("event subscriber lagged, dropped {} events", missed)
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        .streaming(body)
+}
@@ -0,0 +1,80 @@
+use crate::model::ConnectionPool;
+use actix_web::http::StatusCode;
+use actix_web::web::{self, ServiceConfig};
+use actix_web::Responder;
+
+use crate::server::Json;
+
+/// The limits the [`ConnectionPool`] was built with, which sqlx doesn't expose back.
+#[derive(Clone, Copy, Debug)]
+pub struct PoolLimits {
+    pub max_connections: u32,
+}
+
+#[derive(serde::Serialize)]
+struct PoolStatus {
+    size: u32,
+    idle: usize,
+    max: u32,
+}
+
+#[derive(serde::Serialize)]
+struct Readiness {
+    ready: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+pub fn health_service(config: &mut ServiceConfig) {
+    config
+        .service(web::scope("/health").route("/pool", web::get().to(pool_health)))
+        .route("/ready", web::get().to(readiness));
+}
+
+/// Ready once the database is reachable and `todo_todos` exists where the app's
+/// queries will look for it, i.e. the migrations have run; 503 otherwise.
+async fn readiness(pool: web::Data<ConnectionPool>) -> impl Responder {
+    let table_exists = sqlx::query_scalar!(
+        r#"select exists(
+               select from information_schema.tables
+               where table_name = 'todo_todos' and table_schema = any(current_schemas(true))
+           ) as "exists!""#
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+    let (status, readiness) = match table_exists {
+        Ok(true) => (
+            StatusCode::OK,
+            Readiness {
+                ready: true,
+                reason: None,
+            },
+        ),
+        Ok(false) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Readiness {
+                ready: false,
+                reason: Some("table todo_todos is missing; have the migrations run?".into()),
+            },
+        ),
+        Err(err) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Readiness {
+                ready: false,
+                reason: Some(format!("database unavailable: {}", err)),
+            },
+        ),
+    };
+    Json(readiness).customize().with_status(status)
+}
+
+async fn pool_health(
+    pool: web::Data<ConnectionPool>,
+    limits: web::Data<PoolLimits>,
+) -> impl Responder {
+    Json(PoolStatus {
+        size: pool.size(),
+        idle: pool.num_idle(),
+        max: limits.max_connections,
+    })
+}
@@ -0,0 +1,123 @@
+use actix_web::body::BoxBody;
+use actix_web::error::JsonPayloadError;
+use actix_web::http::header::ContentType;
+use actix_web::web::{Data, Query};
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use serde::Serialize;
+
+/// App-wide defaults for [`Json`] responses.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonConfig {
+    pub pretty: bool,
+    /// Write ids as strings, for JavaScript clients that would lose precision on
+    /// integers past 2^53.
+    pub string_ids: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct JsonQuery {
+    pretty: Option<bool>,
+    string_ids: Option<bool>,
+    fields: Option<String>,
+}
+
+/// Like `web::Json`, but pretty-prints when asked to with `?pretty=true`, and writes
+/// `id` and `*_id` fields as strings with `?string_ids=true` (or by default when the
+/// [`JsonConfig`] says so).
+///
+/// `?fields=id,name` keeps only those fields of the response object, or of each object
+/// in a response list, for clients that want lean payloads. Envelopes, a page's `todos`
+/// or a write's `data`, keep their own fields and have what they wrap projected instead.
+pub struct Json<T>(pub T);
+
+impl<T: Serialize> Responder for Json<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let config = req
+            .app_data::<Data<JsonConfig>>()
+            .map(|config| *config.get_ref())
+            .unwrap_or_default();
+        let query = Query::<JsonQuery>::from_query(req.query_string()).ok();
+        let pretty = query
+            .as_ref()
+            .and_then(|query| query.pretty)
+            .unwrap_or(config.pretty);
+        let string_ids = query
+            .as_ref()
+            .and_then(|query| query.string_ids)
+            .unwrap_or(config.string_ids);
+        let fields = query.and_then(|query| query.into_inner().fields);
+
+        let body = if string_ids || fields.is_some() {
+            serde_json::to_value(&self.0).and_then(|mut value| {
+                if let Some(fields) = &fields {
+                    project(
+                        &mut value,
+                        &fields.split(',').map(str::trim).collect::<Vec<_>>(),
+                    );
+                }
+                if string_ids {
+                    stringify_ids(&mut value);
+                }
+                to_string(&value, pretty)
+            })
+        } else {
+            to_string(&self.0, pretty)
+        };
+        match body {
+            Ok(body) => HttpResponse::Ok()
+                .content_type(ContentType::json())
+                .body(body),
+            Err(err) => HttpResponse::from_error(JsonPayloadError::Serialize(err)),
+        }
+    }
+}
+
+fn to_string<T: Serialize>(value: &T, pretty: bool) -> serde_json::Result<String> {
+    if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    }
+}
+
+/// The fields that mark an object as an envelope around the todos `?fields=` is meant
+/// for: a page's `todos`, a write's `data`.
+const ENVELOPED: [&str; 2] = ["todos", "data"];
+
+/// Drops every field not in `fields` from `value`, or from each object in it if it's
+/// a list, or from what it wraps if it's an envelope.
+fn project(value: &mut serde_json::Value, fields: &[&str]) {
+    match value {
+        serde_json::Value::Object(object) => {
+            match ENVELOPED.iter().find(|name| object.contains_key(**name)) {
+                Some(name) => project(&mut object[*name], fields),
+                None => object.retain(|name, _| fields.contains(&name.as_str())),
+            }
+        }
+        serde_json::Value::Array(values) => {
+            values.iter_mut().for_each(|value| project(value, fields))
+        }
+        _ => {}
+    }
+}
+
+/// Replaces every integer `id` or `*_id` field in `value`, however deeply nested, with
+/// its decimal string.
+fn stringify_ids(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            for (name, field) in fields {
+                let is_id = name == "id" || name.ends_with("_id");
+                if is_id && (field.is_i64() || field.is_u64()) {
+                    *field = field.to_string().into();
+                } else {
+                    stringify_ids(field);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(stringify_ids),
+        _ => {}
+    }
+}
@@ -0,0 +1,110 @@
+use crate::model::Todo;
+use crate::server::Json;
+use actix_web::body::BoxBody;
+use actix_web::error::JsonPayloadError;
+use actix_web::http::header;
+use actix_web::web::Query;
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use serde::Serialize;
+use serde_json::Value;
+
+/// The JSON:API media type; clients opt in by accepting it.
+pub const JSON_API: &str = "application/vnd.api+json";
+
+/// Something that can be rendered as a JSON:API resource object.
+pub trait Resource {
+    const TYPE: &'static str;
+
+    fn id(&self) -> String;
+
+    /// Everything but the id.
+    fn attributes(&self) -> Value;
+
+    fn resource(&self) -> Value {
+        serde_json::json!({
+            "type": Self::TYPE,
+            "id": self.id(),
+            "attributes": self.attributes(),
+        })
+    }
+}
+
+impl Resource for Todo {
+    const TYPE: &'static str = "todos";
+
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn attributes(&self) -> Value {
+        let mut attributes = serde_json::to_value(self).unwrap_or_default();
+        if let Some(attributes) = attributes.as_object_mut() {
+            attributes.remove("id");
+        }
+        attributes
+    }
+}
+
+/// A response body with a JSON:API rendering, a top-level `{ "data": ... }` document.
+pub trait Document {
+    fn document(&self) -> Value;
+
+    /// Whether this is a list with nothing in it, which `?empty=204` answers with
+    /// 204 No Content.
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl<R: Resource> Document for R {
+    fn document(&self) -> Value {
+        serde_json::json!({ "data": self.resource() })
+    }
+}
+
+impl<R: Resource> Document for Vec<R> {
+    fn document(&self) -> Value {
+        let data: Vec<_> = self.iter().map(Resource::resource).collect();
+        serde_json::json!({ "data": data })
+    }
+
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct EmptyQuery {
+    empty: Option<String>,
+}
+
+/// Responds like [`Json`], or with the JSON:API [`Document`] when the request's
+/// `Accept` header asks for [`JSON_API`]. Either way, clients passing `?empty=204` get
+/// 204 No Content instead of an empty list.
+pub struct JsonOrApi<T>(pub T);
+
+impl<T: Serialize + Document> Responder for JsonOrApi<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let no_content_when_empty = Query::<EmptyQuery>::from_query(req.query_string())
+            .is_ok_and(|query| query.empty.as_deref() == Some("204"));
+        if no_content_when_empty && self.0.is_empty() {
+            return HttpResponse::NoContent().finish();
+        }
+
+        let wants_json_api = req
+            .headers()
+            .get_all(header::ACCEPT)
+            .filter_map(|accept| accept.to_str().ok())
+            .any(|accept| accept.contains(JSON_API));
+        if !wants_json_api {
+            return Json(self.0).respond_to(req);
+        }
+
+        match serde_json::to_string(&self.0.document()) {
+            Ok(body) => HttpResponse::Ok().content_type(JSON_API).body(body),
+            Err(err) => HttpResponse::from_error(JsonPayloadError::Serialize(err)),
+        }
+    }
+}
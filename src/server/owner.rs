@@ -0,0 +1,42 @@
+use crate::server::{ApiError, User};
+use actix_web::dev::Payload;
+use actix_web::{FromRequest, HttpMessage, HttpRequest};
+use futures::future::{ready, Ready};
+
+/// The user a request acts for: the [`User`] a verified JWT was issued to, or else the
+/// `X-User` header; `None` without either.
+///
+/// Todos created with an owner are only listed for that owner, and requests without
+/// one only see ownerless todos. Behind a JWT, an `X-User` naming anyone else is
+/// refused with [`ApiError::Forbidden`].
+pub struct Owner(pub Option<String>);
+
+impl Owner {
+    pub fn as_deref(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+}
+
+impl FromRequest for Owner {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let claimed = match req.headers().get("X-User") {
+            None => Ok(None),
+            Some(user) => match user.to_str() {
+                Ok("") => Ok(None),
+                Ok(user) => Ok(Some(user.to_owned())),
+                Err(_) => Err(ApiError::BadRequest("X-User must be visible ASCII".into())),
+            },
+        };
+        let owner = claimed.and_then(|claimed| match req.extensions().get::<User>() {
+            None => Ok(Owner(claimed)),
+            Some(User(user)) if claimed.as_ref().is_none_or(|claimed| claimed == user) => {
+                Ok(Owner(Some(user.clone())))
+            }
+            Some(_) => Err(ApiError::Forbidden),
+        });
+        ready(owner)
+    }
+}
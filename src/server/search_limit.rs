@@ -0,0 +1,46 @@
+use crate::server::ApiError;
+use actix_web::dev::Payload;
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Caps how many searches run at once. Searches past the cap are refused with
+/// [`ApiError::Busy`] rather than queueing up on the database.
+///
+/// Searches are unlimited unless this is registered with the app.
+#[derive(Clone, Debug)]
+pub struct SearchPermits(Arc<Semaphore>);
+
+impl SearchPermits {
+    pub fn new(permits: usize) -> Self {
+        SearchPermits(Arc::new(Semaphore::new(permits)))
+    }
+
+    pub(crate) fn try_acquire(&self) -> Result<OwnedSemaphorePermit, ApiError> {
+        self.0
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| ApiError::Busy)
+    }
+}
+
+/// One of the app's [`SearchPermits`], held for as long as the handler taking it runs, or
+/// by whatever it hands the permit on to.
+pub struct SearchPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl FromRequest for SearchPermit {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let permit = match req.app_data::<Data<SearchPermits>>() {
+            Some(permits) => permits.try_acquire().map(Some),
+            None => Ok(None),
+        };
+        ready(permit.map(|permit| SearchPermit { _permit: permit }))
+    }
+}
@@ -0,0 +1,29 @@
+use crate::model::Todo;
+use actix_web::body::BoxBody;
+use actix_web::http::header::ContentType;
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use std::fmt;
+
+/// Todos as `text/plain`, one `[x] name` line each (`[ ]` for unfinished ones), for
+/// shell consumers.
+pub struct TextList(pub Vec<Todo>);
+
+impl fmt::Display for TextList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for todo in &self.0 {
+            let check = if todo.done { 'x' } else { ' ' };
+            writeln!(f, "[{}] {}", check, todo.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl Responder for TextList {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type(ContentType::plaintext())
+            .body(self.to_string())
+    }
+}
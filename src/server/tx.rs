@@ -0,0 +1,151 @@
+use crate::model::{begin_with_isolation, ConnectionPool, IsolationLevel};
+use crate::server::ApiError;
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpMessage, HttpRequest};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use sqlx::{Postgres, Transaction};
+use std::cell::RefCell;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+type Slot = Rc<RefCell<Option<Transaction<'static, Postgres>>>>;
+
+/// Opt-in middleware running each request in one database transaction, for handlers
+/// that make several writes.
+///
+/// The transaction is begun before the handler runs and handed to it through the
+/// [`Tx`] extractor; it's committed if the response is a 2xx and rolled back otherwise.
+/// It's read committed unless made with [`Transactional::at`].
+#[derive(Clone, Copy, Default)]
+pub struct Transactional {
+    isolation: IsolationLevel,
+}
+
+impl Transactional {
+    /// Running each request's transaction at `isolation` instead.
+    pub fn at(isolation: IsolationLevel) -> Self {
+        Transactional { isolation }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Transactional
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = TransactionalMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(TransactionalMiddleware {
+            service: Rc::new(service),
+            isolation: self.isolation,
+        }))
+    }
+}
+
+pub struct TransactionalMiddleware<S> {
+    service: Rc<S>,
+    isolation: IsolationLevel,
+}
+
+impl<S, B> Service<ServiceRequest> for TransactionalMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let isolation = self.isolation;
+        Box::pin(async move {
+            let pool = req
+                .app_data::<Data<ConnectionPool>>()
+                .cloned()
+                .ok_or_else(|| {
+                    ApiError::Database(sqlx::Error::Configuration(
+                        "no connection pool registered with the app".into(),
+                    ))
+                })?;
+            let slot: Slot = Rc::new(RefCell::new(Some(
+                begin_with_isolation(&pool, isolation)
+                    .await
+                    .map_err(ApiError::Database)?,
+            )));
+            req.extensions_mut().insert(slot.clone());
+
+            // an `Err` here drops the transaction, which rolls it back
+            let res = service.call(req).await?;
+
+            // by now the handler's `Tx` has been dropped and put the transaction back
+            let tx = slot.borrow_mut().take();
+            if let Some(tx) = tx {
+                if res.status().is_success() {
+                    tx.commit().await.map_err(ApiError::from)?;
+                } else {
+                    tx.rollback().await.map_err(ApiError::Database)?;
+                }
+            }
+            Ok(res)
+        })
+    }
+}
+
+/// The request's transaction, for handlers behind [`Transactional`]; use it wherever a
+/// `&mut PgConnection` is expected.
+///
+/// Extracting it outside of [`Transactional`] fails with [`ApiError::Database`].
+pub struct Tx {
+    tx: Option<Transaction<'static, Postgres>>,
+    slot: Slot,
+}
+
+impl FromRequest for Tx {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let slot = req.extensions().get::<Slot>().cloned();
+        let tx = slot
+            .and_then(|slot| {
+                let tx = slot.borrow_mut().take()?;
+                Some(Tx { tx: Some(tx), slot })
+            })
+            .ok_or_else(|| {
+                ApiError::Database(sqlx::Error::Configuration(
+                    "no transaction for this request; wrap the route in Transactional".into(),
+                ))
+            });
+        ready(tx)
+    }
+}
+
+impl Deref for Tx {
+    type Target = Transaction<'static, Postgres>;
+
+    fn deref(&self) -> &Self::Target {
+        self.tx.as_ref().expect("transaction is only taken on drop")
+    }
+}
+
+impl DerefMut for Tx {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.tx.as_mut().expect("transaction is only taken on drop")
+    }
+}
+
+impl Drop for Tx {
+    fn drop(&mut self) {
+        // hand the transaction back for the middleware to finish
+        *self.slot.borrow_mut() = self.tx.take();
+    }
+}
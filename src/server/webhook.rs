@@ -0,0 +1,34 @@
+use serde::Serialize;
+
+/// Where to POST newly created todos. Delivery is fire-and-forget: it happens on a
+/// spawned task after the response is on its way and failures are only logged.
+#[derive(Clone, Debug)]
+pub struct Webhook {
+    pub url: String,
+}
+
+impl Webhook {
+    pub fn notify<T: Serialize>(&self, payload: &T) {
+        let body = match serde_json::to_vec(payload) {
+            Ok(body) => body,
+            Err(err) => {
+                log::error!("failed to serialize webhook payload: {}", err);
+                return;
+            }
+        };
+        let url = self.url.clone();
+
+        actix_web::rt::spawn(async move {
+            let sent = awc::Client::default()
+                .post(&url)
+                .content_type("application/json")
+                .send_body(body)
+                .await;
+            match sent {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => log::warn!("webhook {} answered {}", url, response.status()),
+                Err(err) => log::warn!("webhook {} failed: {}", url, err),
+            }
+        });
+    }
+}